@@ -1,10 +1,11 @@
+mod pattern;
 mod style;
 mod time;
 use grid::Grid;
 
 use iced::{
-    button, executor, slider, Align, Application, Button, Clipboard, Column, Command, Container,
-    Element, Length, Row, Settings, Slider, Subscription, Text,
+    button, executor, slider, text_input, Align, Application, Button, Checkbox, Clipboard, Column,
+    Command, Container, Element, Length, Row, Settings, Slider, Subscription, Text, TextInput,
 };
 use std::time::Duration;
 use time::Timer;
@@ -19,12 +20,29 @@ fn main() {
 struct GameOfLife {
     grid: Grid,
     is_playing: bool,
+    is_ticking: bool,
+    queued_ticks: usize,
+    tick_duration: Duration,
     speed: u64,
     next_speed: Option<u64>,
+    rule_text: String,
+    wrap: bool,
+    pattern_path: String,
+    pattern_error: Option<String>,
+    density: f32,
+    seed_interval: u64,
+    generation: u64,
     toggle_button: button::State,
     next_button: button::State,
     clear_button: button::State,
+    randomize_button: button::State,
     speed_slider: slider::State,
+    density_slider: slider::State,
+    seed_interval_slider: slider::State,
+    rule_input: text_input::State,
+    path_input: text_input::State,
+    load_button: button::State,
+    save_button: button::State,
 }
 
 #[derive(Debug, Clone)]
@@ -35,6 +53,38 @@ enum Message {
     Next,
     Clear,
     SpeedChanged(f32),
+    RuleChanged(String),
+    ToggleWrap(bool),
+    PathChanged(String),
+    Load,
+    Save,
+    Randomize,
+    DensityChanged(f32),
+    SeedIntervalChanged(f32),
+    Ticked {
+        result: grid::Cells,
+        tick_duration: Duration,
+    },
+}
+
+impl GameOfLife {
+    /// Kicks off a background generation if one isn't already running and ticks
+    /// are still queued. Back-pressure: at most one computation is ever in
+    /// flight, so a timer that outruns the simulation just grows `queued_ticks`
+    /// instead of piling up futures.
+    fn advance(&mut self) -> Command<Message> {
+        if self.is_ticking || self.queued_ticks == 0 {
+            return Command::none();
+        }
+
+        self.is_ticking = true;
+        Command::perform(self.grid.advance(), |(result, tick_duration)| {
+            Message::Ticked {
+                result,
+                tick_duration,
+            }
+        })
+    }
 }
 
 impl Application for GameOfLife {
@@ -46,6 +96,8 @@ impl Application for GameOfLife {
         (
             Self {
                 speed: 5,
+                rule_text: String::from(grid::Rule::CONWAY),
+                density: 0.3,
                 ..Default::default()
             },
             Command::none(),
@@ -66,17 +118,35 @@ impl Application for GameOfLife {
                 self.grid.update(message);
             }
             Message::Tick | Message::Next => {
-                self.grid.tick();
+                self.queued_ticks += 1;
+                return self.advance();
+            }
+            Message::Ticked {
+                result,
+                tick_duration,
+            } => {
+                self.is_ticking = false;
+                self.queued_ticks = self.queued_ticks.saturating_sub(1);
+                self.grid.apply(result);
+                self.tick_duration = tick_duration;
+                self.generation += 1;
+
+                if self.seed_interval > 0 && self.generation % self.seed_interval == 0 {
+                    self.grid.seed(self.density);
+                }
 
                 if let Some(speed) = self.next_speed.take() {
                     self.speed = speed;
                 }
+
+                return self.advance();
             }
             Message::Toggle => {
                 self.is_playing = !self.is_playing;
             }
             Message::Clear => {
-                self.grid = Grid::default();
+                self.grid.clear();
+                self.generation = 0;
             }
             Message::SpeedChanged(speed) => {
                 if self.is_playing {
@@ -85,6 +155,43 @@ impl Application for GameOfLife {
                     self.speed = speed.round() as u64;
                 }
             }
+            Message::RuleChanged(text) => {
+                if let Ok(rule) = text.parse::<grid::Rule>() {
+                    self.grid.update(grid::Message::SetRule(rule));
+                }
+                self.rule_text = text;
+            }
+            Message::ToggleWrap(wrap) => {
+                self.wrap = wrap;
+                self.grid.update(grid::Message::SetWrap(wrap));
+            }
+            Message::PathChanged(path) => {
+                self.pattern_path = path;
+                self.pattern_error = None;
+            }
+            Message::Load => match pattern::load(&self.pattern_path) {
+                Ok(cells) => {
+                    self.grid.apply(cells);
+                    self.pattern_error = None;
+                }
+                Err(error) => self.pattern_error = Some(error.to_string()),
+            },
+            Message::Save => {
+                let rule = self.grid.rule().to_string();
+                self.pattern_error = pattern::save(&self.pattern_path, self.grid.cells(), &rule)
+                    .err()
+                    .map(|error| error.to_string());
+            }
+            Message::Randomize => {
+                self.grid.randomize(self.density);
+                self.generation = 0;
+            }
+            Message::DensityChanged(density) => {
+                self.density = density;
+            }
+            Message::SeedIntervalChanged(value) => {
+                self.seed_interval = value.round() as u64;
+            }
         }
         Command::none()
     }
@@ -109,7 +216,16 @@ impl Application for GameOfLife {
                 Button::new(&mut self.clear_button, Text::new("Clear"))
                     .on_press(Message::Clear)
                     .style(style::Button),
-            );
+            )
+            .push(Text::new(format!(
+                "{}ms{}",
+                self.tick_duration.as_millis(),
+                if self.queued_ticks > 1 {
+                    format!(" (+{} queued)", self.queued_ticks - 1)
+                } else {
+                    String::new()
+                }
+            )));
 
         let selected_speed = self.next_speed.unwrap_or(self.speed);
         let speed_controls = Row::new()
@@ -127,10 +243,91 @@ impl Application for GameOfLife {
             .push(Text::new(format!("x{}", selected_speed)).size(16))
             .align_items(Align::Center);
 
+        let rule_controls = Row::new()
+            .spacing(10)
+            .push(Text::new("Rule").size(16))
+            .push(
+                TextInput::new(
+                    &mut self.rule_input,
+                    grid::Rule::CONWAY,
+                    &self.rule_text,
+                    Message::RuleChanged,
+                )
+                .padding(5)
+                .width(Length::Units(100))
+                .style(style::TextInput),
+            )
+            .push(Checkbox::new(self.wrap, "Wrap", Message::ToggleWrap).style(style::Checkbox))
+            .align_items(Align::Center);
+
+        let pattern_controls = Row::new()
+            .spacing(10)
+            .push(
+                TextInput::new(
+                    &mut self.path_input,
+                    "pattern.cells",
+                    &self.pattern_path,
+                    Message::PathChanged,
+                )
+                .padding(5)
+                .width(Length::Units(160))
+                .style(style::TextInput),
+            )
+            .push(
+                Button::new(&mut self.load_button, Text::new("Load"))
+                    .on_press(Message::Load)
+                    .style(style::Button),
+            )
+            .push(
+                Button::new(&mut self.save_button, Text::new("Save"))
+                    .on_press(Message::Save)
+                    .style(style::Button),
+            )
+            .push(Text::new(self.pattern_error.as_deref().unwrap_or("")).size(16))
+            .align_items(Align::Center);
+
+        let seed_controls = Row::new()
+            .spacing(10)
+            .push(
+                Button::new(&mut self.randomize_button, Text::new("Randomize"))
+                    .on_press(Message::Randomize)
+                    .style(style::Button),
+            )
+            .push(
+                Slider::new(
+                    &mut self.density_slider,
+                    0.1..=0.6,
+                    self.density,
+                    Message::DensityChanged,
+                )
+                .width(Length::Units(120))
+                .style(style::Slider),
+            )
+            .push(Text::new(format!("{}%", (self.density * 100.0).round() as u32)).size(16))
+            .push(
+                Slider::new(
+                    &mut self.seed_interval_slider,
+                    0.0..=50.0,
+                    self.seed_interval as f32,
+                    Message::SeedIntervalChanged,
+                )
+                .width(Length::Units(120))
+                .style(style::Slider),
+            )
+            .push(Text::new(if self.seed_interval == 0 {
+                String::from("seed: off")
+            } else {
+                format!("seed: every {}", self.seed_interval)
+            }))
+            .align_items(Align::Center);
+
         let controls = Row::new()
             .spacing(20)
             .push(playback_controls)
-            .push(speed_controls);
+            .push(speed_controls)
+            .push(rule_controls)
+            .push(seed_controls)
+            .push(pattern_controls);
 
         let content = Column::new()
             .spacing(10)
@@ -158,63 +355,277 @@ mod grid {
     use iced::canvas::{self, event, Canvas, Cursor, Event, Frame, Geometry, Path};
     use iced::mouse::Interaction;
     use iced::{mouse, Color, Element, Length, Point, Rectangle, Size, Vector};
+    use std::collections::HashSet;
+    use std::time::{Duration, Instant};
+
+    const CELL_SIZE: f32 = 20.0;
+
+    /// Side length of the finite torus used in wrap mode.
+    const SIZE: isize = 32;
 
-    const SIZE: usize = 32;
+    /// A live cell's position on the infinite plane, independent of the viewport.
+    type Coordinate = (isize, isize);
 
-    #[derive(Debug, PartialEq, Clone, Copy)]
-    enum Cell {
-        Unpopulated,
-        Populated,
+    /// The set of live cells, as handed between a `Grid` and the background
+    /// generation future produced by [`Grid::advance`].
+    pub type Cells = HashSet<Coordinate>;
+
+    #[derive(Debug, Clone)]
+    pub enum Message {
+        Populate { i: isize, j: isize },
+        Unpopulate { i: isize, j: isize },
+        SetRule(Rule),
+        SetWrap(bool),
+    }
+
+    /// Birth/survival counts for a life-like cellular automaton, as parsed from a
+    /// `B.../S...` rulestring (e.g. `"B3/S23"` for Conway's Life).
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Rule {
+        birth: [bool; 9],
+        survival: [bool; 9],
+    }
+
+    /// Why a rulestring failed to parse.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum RuleError {
+        MissingBirth,
+        MissingSurvival,
+        InvalidDigit(char),
+        DuplicateDigit(char),
+    }
+
+    impl std::fmt::Display for RuleError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                RuleError::MissingBirth => write!(f, "rule is missing a 'B' part"),
+                RuleError::MissingSurvival => write!(f, "rule is missing an 'S' part"),
+                RuleError::InvalidDigit(c) => write!(f, "'{}' is not a digit between 0 and 8", c),
+                RuleError::DuplicateDigit(c) => write!(f, "digit '{}' is repeated", c),
+            }
+        }
     }
 
-    impl Default for Cell {
+    impl Rule {
+        pub const CONWAY: &'static str = "B3/S23";
+
+        fn counts(digits: &str) -> Result<[bool; 9], RuleError> {
+            let mut counts = [false; 9];
+
+            for digit in digits.chars() {
+                let n = digit
+                    .to_digit(10)
+                    .filter(|&n| n <= 8)
+                    .ok_or(RuleError::InvalidDigit(digit))? as usize;
+
+                if counts[n] {
+                    return Err(RuleError::DuplicateDigit(digit));
+                }
+                counts[n] = true;
+            }
+
+            Ok(counts)
+        }
+
+        fn split_part(part: &str, letters: [char; 2]) -> Option<&str> {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(c) if c == letters[0] || c == letters[1] => Some(chars.as_str()),
+                _ => None,
+            }
+        }
+    }
+
+    impl Default for Rule {
         fn default() -> Self {
-            Cell::Unpopulated
+            Self::CONWAY.parse().expect("Conway's rule is valid")
         }
     }
 
-    #[derive(Debug, Clone)]
-    pub enum Message {
-        Populate { i: usize, j: usize },
+    impl std::fmt::Display for Rule {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "B")?;
+            for (n, _) in self.birth.iter().enumerate().filter(|&(_, &born)| born) {
+                write!(f, "{}", n)?;
+            }
+
+            write!(f, "/S")?;
+            for (n, _) in self.survival.iter().enumerate().filter(|&(_, &alive)| alive) {
+                write!(f, "{}", n)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    impl std::str::FromStr for Rule {
+        type Err = RuleError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut parts = s.splitn(2, '/');
+            let birth_part = parts.next().unwrap_or("");
+            let survival_part = parts.next().unwrap_or("");
+
+            let birth_digits =
+                Self::split_part(birth_part, ['B', 'b']).ok_or(RuleError::MissingBirth)?;
+            let survival_digits =
+                Self::split_part(survival_part, ['S', 's']).ok_or(RuleError::MissingSurvival)?;
+
+            Ok(Rule {
+                birth: Self::counts(birth_digits)?,
+                survival: Self::counts(survival_digits)?,
+            })
+        }
     }
 
-    #[derive(Default)]
     pub struct Grid {
-        cells: [[Cell; SIZE]; SIZE],
+        cells: Cells,
+        rule: Rule,
+        wrap: bool,
         mouse_pressed: bool,
+        erasing: bool,
+        translation: Vector,
+        panning: Option<(Point, Vector)>,
         cache: canvas::Cache,
     }
 
-    impl Grid {
-        pub fn tick(&mut self) {
-            let mut populated_neighbors: [[usize; SIZE]; SIZE] = [[0; SIZE]; SIZE];
+    impl Default for Grid {
+        fn default() -> Self {
+            Self {
+                cells: HashSet::new(),
+                rule: Rule::default(),
+                wrap: false,
+                mouse_pressed: false,
+                erasing: false,
+                translation: Vector::new(0.0, 0.0),
+                panning: None,
+                cache: canvas::Cache::default(),
+            }
+        }
+    }
 
-            for (i, row) in self.cells.iter().enumerate() {
-                for (j, _) in row.iter().enumerate() {
-                    populated_neighbors[i][j] = self.populated_neighbors(i, j);
-                }
+    impl Grid {
+        /// Computes the next generation on the executor, away from the UI thread,
+        /// and reports how long it took so callers can tell when they've outrun
+        /// the simulation.
+        pub fn advance(&self) -> impl std::future::Future<Output = (Cells, Duration)> {
+            let cells = self.cells.clone();
+            let rule = self.rule;
+            let wrap = self.wrap;
+
+            async move {
+                let start = Instant::now();
+                let next = Self::next_generation(&cells, rule, wrap);
+                (next, start.elapsed())
             }
+        }
+
+        /// Applies a generation computed by [`Grid::advance`], or a freshly
+        /// loaded pattern. Canonicalizes onto the torus when wrap mode is on, so
+        /// a loaded pattern larger than `SIZE` can't leave cells permanently
+        /// outside the bounds that paint/erase and `tick` assume.
+        pub fn apply(&mut self, cells: Cells) {
+            self.cells = if self.wrap {
+                cells
+                    .into_iter()
+                    .map(|(i, j)| (Self::wrap_coordinate(i), Self::wrap_coordinate(j)))
+                    .collect()
+            } else {
+                cells
+            };
+            self.cache.clear();
+        }
+
+        /// Empties the board without resetting `rule` or `wrap`, so clearing
+        /// doesn't silently revert the user's configuration back to defaults.
+        pub fn clear(&mut self) {
+            self.cells.clear();
+            self.cache.clear();
+        }
 
-            for (i, row) in populated_neighbors.iter().enumerate() {
-                for (j, amount) in row.iter().enumerate() {
-                    let is_populated = self.cells[i][j] == Cell::Populated;
+        pub fn cells(&self) -> &Cells {
+            &self.cells
+        }
+
+        pub fn rule(&self) -> Rule {
+            self.rule
+        }
 
-                    self.cells[i][j] = match amount {
-                        2 if is_populated => Cell::Populated,
-                        3 => Cell::Populated,
-                        _ => Cell::Unpopulated,
-                    };
+        /// Clears the board and seeds a fresh random soup.
+        pub fn randomize(&mut self, density: f32) {
+            self.cells.clear();
+            self.seed(density);
+        }
+
+        /// Sets each cell in a `SIZE`-wide window live with probability `density`,
+        /// without clearing what's already there, so playback can be kept alive
+        /// with periodic bursts instead of hand-drawing every run. The window is
+        /// centered on whatever part of the plane is currently panned into view,
+        /// rather than always the world origin, since panning exists precisely
+        /// so patterns aren't confined to a fixed patch.
+        pub fn seed(&mut self, density: f32) {
+            use rand::Rng;
+
+            let mut rng = rand::thread_rng();
+            let half = SIZE / 2;
+            let (center_i, center_j) = self.view_center();
+
+            for i in (center_i - half)..(center_i + half) {
+                for j in (center_j - half)..(center_j + half) {
+                    if rng.gen::<f32>() < density {
+                        self.cells.insert(self.canonical(i, j));
+                    }
                 }
             }
 
             self.cache.clear();
         }
 
+        fn next_generation(cells: &Cells, rule: Rule, wrap: bool) -> Cells {
+            let mut candidates = HashSet::new();
+            for &(i, j) in cells {
+                candidates.insert((i, j));
+                candidates.extend(Self::neighbors(i, j, wrap).iter().copied());
+            }
+
+            candidates
+                .into_iter()
+                .filter(|&coordinate| {
+                    let amount = Self::populated_neighbors(cells, coordinate, wrap);
+                    let is_populated = cells.contains(&coordinate);
+
+                    if is_populated {
+                        rule.survival[amount]
+                    } else {
+                        rule.birth[amount]
+                    }
+                })
+                .collect()
+        }
+
         pub fn update(&mut self, message: Message) {
             match message {
                 Message::Populate { i, j } => {
-                    self.cells[i][j] = Cell::Populated;
-                    self.cache.clear()
+                    self.cells.insert(self.canonical(i, j));
+                    self.cache.clear();
+                }
+                Message::Unpopulate { i, j } => {
+                    self.cells.remove(&self.canonical(i, j));
+                    self.cache.clear();
+                }
+                Message::SetRule(rule) => {
+                    self.rule = rule;
+                }
+                Message::SetWrap(wrap) => {
+                    self.wrap = wrap;
+                    if wrap {
+                        self.cells = self
+                            .cells
+                            .iter()
+                            .map(|&(i, j)| (Self::wrap_coordinate(i), Self::wrap_coordinate(j)))
+                            .collect();
+                    }
+                    self.cache.clear();
                 }
             }
         }
@@ -226,44 +637,68 @@ mod grid {
                 .into()
         }
 
-        fn populated_neighbors(&self, row: usize, column: usize) -> usize {
-            use itertools::Itertools;
+        /// The world coordinate currently at the center of the viewport. The
+        /// viewport's own size cancels out of the math (it offsets both the
+        /// cell and the center by the same amount), so only `translation`
+        /// matters here.
+        fn view_center(&self) -> Coordinate {
+            (
+                (-self.translation.y / CELL_SIZE).floor() as isize,
+                (-self.translation.x / CELL_SIZE).floor() as isize,
+            )
+        }
 
-            let rows = row.saturating_sub(1)..=row + 1;
-            let columns = column.saturating_sub(1)..=column + 1;
+        /// Maps a coordinate onto the bounded torus when wrap mode is on,
+        /// otherwise leaves it as-is on the infinite plane.
+        fn canonical(&self, i: isize, j: isize) -> Coordinate {
+            if self.wrap {
+                (Self::wrap_coordinate(i), Self::wrap_coordinate(j))
+            } else {
+                (i, j)
+            }
+        }
 
-            let is_inside_bounds = |i: usize, j: usize| i < SIZE && j < SIZE;
-            let is_neighbor = |i: usize, j: usize| i != row || j != column;
+        fn wrap_coordinate(n: isize) -> isize {
+            n.rem_euclid(SIZE)
+        }
 
-            let is_populated = |i: usize, j: usize| self.cells[i][j] == Cell::Populated;
+        fn neighbors(i: isize, j: isize, wrap: bool) -> [Coordinate; 8] {
+            let neighbors = [
+                (i - 1, j - 1),
+                (i - 1, j),
+                (i - 1, j + 1),
+                (i, j - 1),
+                (i, j + 1),
+                (i + 1, j - 1),
+                (i + 1, j),
+                (i + 1, j + 1),
+            ];
+
+            if wrap {
+                neighbors.map(|(i, j)| (Self::wrap_coordinate(i), Self::wrap_coordinate(j)))
+            } else {
+                neighbors
+            }
+        }
 
-            rows.cartesian_product(columns)
-                .filter(|&(i, j)| is_inside_bounds(i, j) && is_neighbor(i, j) && is_populated(i, j))
+        fn populated_neighbors(cells: &Cells, (i, j): Coordinate, wrap: bool) -> usize {
+            Self::neighbors(i, j, wrap)
+                .iter()
+                .filter(|coordinate| cells.contains(coordinate))
                 .count()
         }
 
-        fn region(&self, size: Size) -> Rectangle {
-            let side = size.width.min(size.height);
-
-            Rectangle {
-                x: (size.width - side) / 2.0,
-                y: (size.height - side) / 2.0,
-                width: side,
-                height: side,
-            }
+        fn offset(&self, size: Size) -> Vector {
+            Vector::new(size.width / 2.0, size.height / 2.0) + self.translation
         }
 
-        fn cell_at(&self, region: Rectangle, position: Point) -> Option<(usize, usize)> {
-            if region.contains(position) {
-                let cell_size = region.width / SIZE as f32;
+        fn cell_at(&self, bounds: Rectangle, position: Point) -> Coordinate {
+            let offset = self.offset(bounds.size());
 
-                let i = ((position.y - region.y) / cell_size).ceil() as usize;
-                let j = ((position.x - region.x) / cell_size).ceil() as usize;
+            let i = ((position.y - offset.y) / CELL_SIZE).floor() as isize;
+            let j = ((position.x - offset.x) / CELL_SIZE).floor() as isize;
 
-                Some((i.saturating_sub(1), j.saturating_sub(1)))
-            } else {
-                None
-            }
+            (i, j)
         }
     }
 
@@ -274,26 +709,50 @@ mod grid {
             bounds: Rectangle,
             cursor: Cursor,
         ) -> (event::Status, Option<Message>) {
-            if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event {
-                self.mouse_pressed = true;
-            } else if Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) == event {
-                self.mouse_pressed = false;
-            }
-
-            let region = self.region(bounds.size());
             let cursor_position = if let Some(position) = cursor.position_in(&bounds) {
                 position
             } else {
                 return (event::Status::Ignored, None);
             };
-            let (i, j) = if let Some(at) = self.cell_at(region, cursor_position) {
-                at
-            } else {
-                return (event::Status::Ignored, None);
-            };
 
-            let populate = if self.cells[i][j] != Cell::Populated {
+            match event {
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                    self.mouse_pressed = true;
+                }
+                Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                    self.mouse_pressed = false;
+                }
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                    self.erasing = true;
+                }
+                Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Right)) => {
+                    self.erasing = false;
+                }
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Middle)) => {
+                    self.panning = Some((cursor_position, self.translation));
+                }
+                Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Middle)) => {
+                    self.panning = None;
+                }
+                _ => {}
+            }
+
+            if let Some((origin, start_translation)) = self.panning {
+                if let Event::Mouse(mouse::Event::CursorMoved { .. }) = event {
+                    self.translation = start_translation + (cursor_position - origin);
+                    self.cache.clear();
+                    return (event::Status::Captured, None);
+                }
+            }
+
+            let (i, j) = self.cell_at(bounds, cursor_position);
+            let (i, j) = self.canonical(i, j);
+            let is_populated = self.cells.contains(&(i, j));
+
+            let message = if self.mouse_pressed && !is_populated {
                 Some(Message::Populate { i, j })
+            } else if self.erasing && is_populated {
+                Some(Message::Unpopulate { i, j })
             } else {
                 None
             };
@@ -302,21 +761,28 @@ mod grid {
                 Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
                     if self.mouse_pressed =>
                 {
-                    (event::Status::Captured, populate)
+                    (event::Status::Captured, message)
+                }
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right))
+                    if self.erasing =>
+                {
+                    (event::Status::Captured, message)
                 }
-                Event::Mouse(mouse::Event::CursorMoved { .. }) if self.mouse_pressed => {
-                    (event::Status::Captured, populate)
+                Event::Mouse(mouse::Event::CursorMoved { .. })
+                    if self.mouse_pressed || self.erasing =>
+                {
+                    (event::Status::Captured, message)
                 }
                 _ => (event::Status::Ignored, None),
             }
         }
 
         fn draw(&self, bounds: Rectangle, cursor: Cursor) -> Vec<Geometry> {
-            let region = self.region(bounds.size());
             let cell_size = Size::new(1.0, 1.0);
+            let offset = self.offset(bounds.size());
 
             let life = self.cache.draw(bounds.size(), |frame| {
-                let background = Path::rectangle(region.position(), region.size());
+                let background = Path::rectangle(Point::ORIGIN, frame.size());
                 frame.fill(
                     &background,
                     Color::from_rgb(
@@ -327,16 +793,12 @@ mod grid {
                 );
 
                 frame.with_save(|frame| {
-                    frame.translate(Vector::new(region.x, region.y));
-                    frame.scale(region.width / SIZE as f32);
+                    frame.translate(offset);
+                    frame.scale(CELL_SIZE);
 
                     let cells = Path::new(|p| {
-                        for (i, row) in self.cells.iter().enumerate() {
-                            for (j, cell) in row.iter().enumerate() {
-                                if *cell == Cell::Populated {
-                                    p.rectangle(Point::new(j as f32, i as f32), cell_size);
-                                }
-                            }
+                        for &(i, j) in &self.cells {
+                            p.rectangle(Point::new(j as f32, i as f32), cell_size);
                         }
                     });
                     frame.fill(&cells, Color::WHITE);
@@ -346,21 +808,19 @@ mod grid {
             let hovered_cell = {
                 let mut frame = Frame::new(bounds.size());
 
-                frame.translate(Vector::new(region.x, region.y));
-                frame.scale(region.width / SIZE as f32);
+                frame.translate(offset);
+                frame.scale(CELL_SIZE);
 
                 if let Some(cursor_position) = cursor.position_in(&bounds) {
-                    if let Some((i, j)) = self.cell_at(region, cursor_position) {
-                        let interaction =
-                            Path::rectangle(Point::new(j as f32, i as f32), cell_size);
-                        frame.fill(
-                            &interaction,
-                            Color {
-                                a: 0.5,
-                                ..Color::BLACK
-                            },
-                        )
-                    }
+                    let (i, j) = self.cell_at(bounds, cursor_position);
+                    let interaction = Path::rectangle(Point::new(j as f32, i as f32), cell_size);
+                    frame.fill(
+                        &interaction,
+                        Color {
+                            a: 0.5,
+                            ..Color::BLACK
+                        },
+                    )
                 }
 
                 frame.into_geometry()
@@ -370,12 +830,82 @@ mod grid {
         }
 
         fn mouse_interaction(&self, bounds: Rectangle, cursor: Cursor) -> Interaction {
-            let region = self.region(bounds.size());
-
-            match cursor.position_in(&bounds) {
-                Some(position) if region.contains(position) => Interaction::Crosshair,
-                _ => Interaction::default(),
+            if self.panning.is_some() {
+                Interaction::Grab
+            } else if cursor.position_in(&bounds).is_some() {
+                Interaction::Crosshair
+            } else {
+                Interaction::default()
             }
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn parses_conway() {
+            let rule: Rule = "B3/S23".parse().unwrap();
+            assert_eq!(rule.birth, [false, false, false, true, false, false, false, false, false]);
+            assert_eq!(
+                rule.survival,
+                [false, false, true, true, false, false, false, false, false]
+            );
+        }
+
+        #[test]
+        fn parses_highlife() {
+            let rule: Rule = "B36/S23".parse().unwrap();
+            assert!(rule.birth[3]);
+            assert!(rule.birth[6]);
+            assert!(rule.survival[2]);
+            assert!(rule.survival[3]);
+        }
+
+        #[test]
+        fn parses_seeds_with_empty_survival() {
+            let rule: Rule = "B2/S".parse().unwrap();
+            assert!(rule.birth[2]);
+            assert_eq!(rule.survival, [false; 9]);
+        }
+
+        #[test]
+        fn accepts_lowercase_letters() {
+            let rule: Rule = "b3/s23".parse().unwrap();
+            assert_eq!(rule, "B3/S23".parse().unwrap());
+        }
+
+        #[test]
+        fn rejects_missing_birth() {
+            assert_eq!("3/S23".parse::<Rule>(), Err(RuleError::MissingBirth));
+        }
+
+        #[test]
+        fn rejects_missing_survival() {
+            assert_eq!("B3/23".parse::<Rule>(), Err(RuleError::MissingSurvival));
+        }
+
+        #[test]
+        fn rejects_out_of_range_digit() {
+            assert_eq!(
+                "B9/S23".parse::<Rule>(),
+                Err(RuleError::InvalidDigit('9'))
+            );
+        }
+
+        #[test]
+        fn rejects_duplicate_digit() {
+            assert_eq!(
+                "B33/S23".parse::<Rule>(),
+                Err(RuleError::DuplicateDigit('3'))
+            );
+        }
+
+        #[test]
+        fn display_round_trips_through_parse() {
+            let rule: Rule = "B36/S23".parse().unwrap();
+            assert_eq!(rule.to_string().parse::<Rule>().unwrap(), rule);
+        }
+    }
 }
@@ -0,0 +1,265 @@
+//! Loading and saving of life patterns as plaintext or RLE files.
+
+use crate::grid::Cells;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    InvalidRle(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "{}", error),
+            Error::InvalidRle(message) => write!(f, "invalid RLE: {}", message),
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+/// Loads live cells from a plaintext or `.rle` file, centering the pattern on
+/// the origin so it lands in view regardless of where it was authored.
+pub fn load(path: &str) -> Result<Cells, Error> {
+    let contents = std::fs::read_to_string(path)?;
+
+    let cells = if path.ends_with(".rle") {
+        parse_rle(&contents)?
+    } else {
+        parse_plaintext(&contents)
+    };
+
+    Ok(center(cells))
+}
+
+/// Saves only the bounding box of live cells, in the format implied by `path`'s
+/// extension (`.rle` or plaintext otherwise). `rule` is the active rulestring
+/// (e.g. `"B3/S23"`), recorded in the RLE header so the save round-trips the
+/// board's actual behavior rather than always claiming Conway's Life.
+pub fn save(path: &str, cells: &Cells, rule: &str) -> Result<(), Error> {
+    let contents = if path.ends_with(".rle") {
+        write_rle(cells, rule)
+    } else {
+        write_plaintext(cells)
+    };
+
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Each line is a row; `.`, `0` and space are dead, anything else is alive.
+/// Lines starting with `!` are comments and hold no cells.
+fn parse_plaintext(text: &str) -> Cells {
+    let mut cells = Cells::default();
+
+    for (i, line) in text.lines().enumerate() {
+        if line.starts_with('!') {
+            continue;
+        }
+
+        for (j, c) in line.chars().enumerate() {
+            if !matches!(c, '.' | '0' | ' ') {
+                cells.insert((i as isize, j as isize));
+            }
+        }
+    }
+
+    cells
+}
+
+fn write_plaintext(cells: &Cells) -> String {
+    if cells.is_empty() {
+        return String::new();
+    }
+
+    let (min_i, max_i, min_j, max_j) = bounding_box(cells);
+    let mut out = String::new();
+
+    for i in min_i..=max_i {
+        for j in min_j..=max_j {
+            out.push(if cells.contains(&(i, j)) { 'O' } else { '.' });
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+fn parse_rle(text: &str) -> Result<Cells, Error> {
+    let mut cells = Cells::default();
+    let mut i: isize = 0;
+    let mut j: isize = 0;
+    let mut count = String::new();
+
+    'lines: for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('x') {
+            continue;
+        }
+
+        for c in line.chars() {
+            match c {
+                '0'..='9' => count.push(c),
+                'b' => j += take_count(&mut count),
+                'o' => {
+                    for _ in 0..take_count(&mut count) {
+                        cells.insert((i, j));
+                        j += 1;
+                    }
+                }
+                '$' => {
+                    i += take_count(&mut count).max(1);
+                    j = 0;
+                }
+                '!' => break 'lines,
+                _ => return Err(Error::InvalidRle(format!("unexpected character '{}'", c))),
+            }
+        }
+    }
+
+    Ok(cells)
+}
+
+fn write_rle(cells: &Cells, rule: &str) -> String {
+    if cells.is_empty() {
+        return String::from("!\n");
+    }
+
+    let (min_i, max_i, min_j, max_j) = bounding_box(cells);
+    let mut header = format!(
+        "x = {}, y = {}, rule = {}\n",
+        max_j - min_j + 1,
+        max_i - min_i + 1,
+        rule
+    );
+
+    let mut body = String::new();
+    for i in min_i..=max_i {
+        let mut run_char = None;
+        let mut run_len = 0usize;
+
+        for j in min_j..=max_j {
+            let c = if cells.contains(&(i, j)) { 'o' } else { 'b' };
+            if run_char == Some(c) {
+                run_len += 1;
+            } else {
+                push_run(&mut body, run_char, run_len);
+                run_char = Some(c);
+                run_len = 1;
+            }
+        }
+        push_run(&mut body, run_char, run_len);
+        body.push('$');
+    }
+    body.pop();
+    body.push('!');
+
+    header.push_str(&body);
+    header.push('\n');
+    header
+}
+
+fn push_run(buf: &mut String, c: Option<char>, len: usize) {
+    if let Some(c) = c {
+        if len > 1 {
+            buf.push_str(&len.to_string());
+        }
+        buf.push(c);
+    }
+}
+
+fn take_count(count: &mut String) -> isize {
+    let n = if count.is_empty() {
+        1
+    } else {
+        count.parse().unwrap_or(1)
+    };
+    count.clear();
+    n
+}
+
+fn bounding_box(cells: &Cells) -> (isize, isize, isize, isize) {
+    let min_i = cells.iter().map(|&(i, _)| i).min().unwrap();
+    let max_i = cells.iter().map(|&(i, _)| i).max().unwrap();
+    let min_j = cells.iter().map(|&(_, j)| j).min().unwrap();
+    let max_j = cells.iter().map(|&(_, j)| j).max().unwrap();
+
+    (min_i, max_i, min_j, max_j)
+}
+
+fn center(cells: Cells) -> Cells {
+    if cells.is_empty() {
+        return cells;
+    }
+
+    let (min_i, max_i, min_j, max_j) = bounding_box(&cells);
+    let height = max_i - min_i;
+    let width = max_j - min_j;
+
+    cells
+        .into_iter()
+        .map(|(i, j)| (i - min_i - height / 2, j - min_j - width / 2))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLIDER: &str = ".O.\n..O\nOOO\n";
+
+    /// Shifts a pattern so its bounding box starts at the origin, to compare
+    /// shapes independent of where they happen to sit on the plane.
+    fn normalize(cells: &Cells) -> Vec<(isize, isize)> {
+        let (min_i, _, min_j, _) = bounding_box(cells);
+        let mut shifted: Vec<_> = cells.iter().map(|&(i, j)| (i - min_i, j - min_j)).collect();
+        shifted.sort();
+        shifted
+    }
+
+    #[test]
+    fn plaintext_round_trips_a_glider() {
+        let cells = parse_plaintext(GLIDER);
+        let reparsed = parse_plaintext(&write_plaintext(&cells));
+        assert_eq!(normalize(&cells), normalize(&reparsed));
+    }
+
+    #[test]
+    fn plaintext_treats_dot_zero_and_space_as_dead() {
+        let cells = parse_plaintext(".0 X\n");
+        assert_eq!(cells, [(0, 3)].into_iter().collect());
+    }
+
+    #[test]
+    fn rle_round_trips_a_glider() {
+        let cells = parse_plaintext(GLIDER);
+        let reparsed = parse_rle(&write_rle(&cells, "B3/S23")).unwrap();
+        assert_eq!(normalize(&cells), normalize(&reparsed));
+    }
+
+    #[test]
+    fn rle_header_carries_the_active_rule() {
+        let cells: Cells = [(0, 0)].into_iter().collect();
+        let written = write_rle(&cells, "B36/S23");
+        assert!(written.lines().next().unwrap().contains("rule = B36/S23"));
+    }
+
+    #[test]
+    fn rle_rejects_unknown_characters() {
+        assert!(parse_rle("x = 1, y = 1, rule = B3/S23\nq!\n").is_err());
+    }
+
+    #[test]
+    fn center_straddles_the_origin() {
+        let cells: Cells = [(10, 10), (11, 11)].into_iter().collect();
+        let (min_i, max_i, min_j, max_j) = bounding_box(&center(cells));
+
+        assert!(min_i <= 0 && max_i >= 0);
+        assert!(min_j <= 0 && max_j >= 0);
+    }
+}